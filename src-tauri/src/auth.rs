@@ -0,0 +1,241 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+/// How long to wait on the loopback listener for the genuine browser redirect
+/// before giving up on the login attempt.
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_opener::OpenerExt;
+
+const SPOTIFY_AUTHORIZE_URL: &str = "https://accounts.spotify.com/authorize";
+const SPOTIFY_TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+const SPOTIFY_SCOPES: &str = "user-read-private user-read-email";
+
+/// Session handed to the frontend once the loopback callback has exchanged the
+/// authorization code for tokens.
+#[derive(Debug, Serialize, Clone)]
+pub struct SpotifySession {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_in: u64,
+}
+
+fn generate_code_verifier() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn code_challenge_for(verifier: &str) -> String {
+    let digest = Sha256::digest(verifier.as_bytes());
+    URL_SAFE_NO_PAD.encode(digest)
+}
+
+fn generate_state() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Reads the `GET /callback?...` request line off a loopback connection and
+/// returns its query string, ignoring everything else about the request.
+fn read_callback_query(stream: &TcpStream) -> Option<String> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+
+    let path = request_line.split_whitespace().nth(1)?;
+    let (_, query) = path.split_once('?')?;
+    Some(query.to_string())
+}
+
+/// Writes a response to the browser and closes the connection. Only the
+/// `success` path claims the user is signed in — callers must not serve it
+/// until the token exchange has actually completed.
+fn write_callback_response(mut stream: TcpStream, success: bool) {
+    let body = if success {
+        "<html><body><h3>Signed in to Statisfy. You can close this tab.</h3></body></html>"
+    } else {
+        "<html><body><h3>Something went wrong signing in to Statisfy. You can close this tab and try again.</h3></body></html>"
+    };
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn exchange_code_for_session(
+    client_id: &str,
+    redirect_uri: &str,
+    code: &str,
+    code_verifier: &str,
+) -> Result<SpotifySession, String> {
+    #[derive(serde::Deserialize)]
+    struct TokenResponse {
+        access_token: String,
+        refresh_token: Option<String>,
+        expires_in: u64,
+    }
+
+    let params = [
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+        ("client_id", client_id),
+        ("code_verifier", code_verifier),
+    ];
+
+    let response = reqwest::blocking::Client::new()
+        .post(SPOTIFY_TOKEN_URL)
+        .form(&params)
+        .send()
+        .map_err(|err| err.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "token exchange failed with status {}",
+            response.status()
+        ));
+    }
+
+    let token: TokenResponse = response.json().map_err(|err| err.to_string())?;
+
+    Ok(SpotifySession {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_in: token.expires_in,
+    })
+}
+
+/// Handles a single accepted loopback connection. Returns `Some(session)`
+/// only once the redirect has been validated against `state` and the code
+/// has been exchanged for tokens; anything short of that serves an error
+/// page and returns `None` so the caller keeps waiting for the real redirect.
+fn handle_callback_connection(
+    stream: TcpStream,
+    state: &str,
+    client_id: &str,
+    redirect_uri: &str,
+    code_verifier: &str,
+) -> Option<SpotifySession> {
+    let Some(query) = read_callback_query(&stream) else {
+        write_callback_response(stream, false);
+        return None;
+    };
+
+    let params: std::collections::HashMap<_, _> =
+        url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    if params.get("state").map(|s| s.as_ref()) != Some(state) {
+        eprintln!("callback state mismatch or missing, ignoring connection");
+        write_callback_response(stream, false);
+        return None;
+    }
+
+    let Some(code) = params.get("code") else {
+        eprintln!("callback missing authorization code");
+        write_callback_response(stream, false);
+        return None;
+    };
+
+    match exchange_code_for_session(client_id, redirect_uri, code, code_verifier) {
+        Ok(session) => {
+            write_callback_response(stream, true);
+            Some(session)
+        }
+        Err(err) => {
+            eprintln!("token exchange failed: {err}");
+            write_callback_response(stream, false);
+            None
+        }
+    }
+}
+
+/// Starts the Authorization Code + PKCE flow via a local loopback listener.
+///
+/// Binds an ephemeral port on `127.0.0.1`, opens the Spotify authorize page
+/// pointed at `http://127.0.0.1:<port>/callback`, then blocks (on a background
+/// thread) until the browser redirects back with the authorization code. The
+/// resulting session is emitted to the frontend as `"spotify-auth"`.
+///
+/// Kept alongside the `statisfy://` deep-link flow, which remains the fallback
+/// for platforms where binding a loopback port is undesirable.
+#[tauri::command]
+pub fn start_spotify_login(app: AppHandle, client_id: String) -> Result<(), String> {
+    let listener = TcpListener::bind("127.0.0.1:0").map_err(|err| err.to_string())?;
+    let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+    let redirect_uri = format!("http://127.0.0.1:{port}/callback");
+
+    let code_verifier = generate_code_verifier();
+    let code_challenge = code_challenge_for(&code_verifier);
+    let state = generate_state();
+
+    let auth_url = format!(
+        "{SPOTIFY_AUTHORIZE_URL}?client_id={client_id}&response_type=code&redirect_uri={redirect_uri}&code_challenge_method=S256&code_challenge={code_challenge}&state={state}&scope={scope}",
+        client_id = urlencoding::encode(&client_id),
+        redirect_uri = urlencoding::encode(&redirect_uri),
+        code_challenge = code_challenge,
+        state = state,
+        scope = urlencoding::encode(SPOTIFY_SCOPES),
+    );
+
+    app.opener()
+        .open_url(auth_url, None::<&str>)
+        .map_err(|err| err.to_string())?;
+
+    std::thread::spawn(move || {
+        if let Err(err) = listener.set_nonblocking(true) {
+            eprintln!("failed to configure loopback listener: {err}");
+            return;
+        }
+
+        // A stray connection (browser pre-connect, a local port scan, a second
+        // tab) would otherwise be mistaken for the real redirect and leave
+        // nothing listening when it actually arrives, so keep accepting until
+        // a connection's `state`+`code` actually check out, or we time out.
+        let deadline = Instant::now() + CALLBACK_TIMEOUT;
+        loop {
+            if Instant::now() >= deadline {
+                eprintln!(
+                    "timed out after {}s waiting for the Spotify OAuth callback",
+                    CALLBACK_TIMEOUT.as_secs()
+                );
+                return;
+            }
+
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(200));
+                    continue;
+                }
+                Err(err) => {
+                    eprintln!("loopback callback listener failed: {err}");
+                    return;
+                }
+            };
+
+            if let Err(err) = stream.set_nonblocking(false) {
+                eprintln!("failed to configure callback connection: {err}");
+                continue;
+            }
+
+            if let Some(session) =
+                handle_callback_connection(stream, &state, &client_id, &redirect_uri, &code_verifier)
+            {
+                let _ = app.emit("spotify-auth", session);
+                return;
+            }
+        }
+    });
+
+    Ok(())
+}
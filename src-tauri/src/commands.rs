@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use url::Url;
+
+const DEEP_LINK_SCHEME: &str = "statisfy";
+
+/// Deep links the OS delivered before the frontend signaled it's listening
+/// for the `"deep-link"` event, held for pickup by [`drain_pending_deep_link`].
+///
+/// `Emitter::emit` doesn't buffer for late listeners — a link received while
+/// the webview is still loading would otherwise be silently dropped, so both
+/// `setup` and the live `on_open_url` callback in `lib.rs` stash here instead
+/// of emitting until [`PendingDeepLinks::is_ready`] is set.
+#[derive(Default)]
+pub struct PendingDeepLinks {
+    stash: Mutex<Vec<String>>,
+    ready: AtomicBool,
+}
+
+impl PendingDeepLinks {
+    /// Whether the frontend has signaled (by calling [`drain_pending_deep_link`])
+    /// that it's listening for `"deep-link"` events directly.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Relaxed)
+    }
+
+    /// Holds a deep link for later pickup instead of emitting it into the void.
+    pub fn stash(&self, url: String) {
+        self.stash.lock().unwrap().push(url);
+    }
+}
+
+/// Whether this app is currently registered as the OS handler for `statisfy://` links.
+#[tauri::command]
+pub fn is_deep_link_registered(app: AppHandle) -> Result<bool, String> {
+    app.deep_link()
+        .is_registered(DEEP_LINK_SCHEME)
+        .map_err(|err| err.to_string())
+}
+
+/// Registers this app as the OS handler for `statisfy://` links.
+#[tauri::command]
+pub fn register_deep_link(app: AppHandle) -> Result<(), String> {
+    app.deep_link()
+        .register(DEEP_LINK_SCHEME)
+        .map_err(|err| err.to_string())
+}
+
+/// Removes this app as the OS handler for `statisfy://` links.
+#[tauri::command]
+pub fn unregister_deep_link(app: AppHandle) -> Result<(), String> {
+    app.deep_link()
+        .unregister(DEEP_LINK_SCHEME)
+        .map_err(|err| err.to_string())
+}
+
+/// Returns any deep link stashed in [`PendingDeepLinks`], clears it so it's
+/// only handed out once, and marks the frontend as ready so links delivered
+/// from here on are emitted to `"deep-link"` directly instead of stashed.
+///
+/// Call this as soon as the frontend's `"deep-link"` listener is attached, to
+/// pick up whatever `setup` (or a live `on_open_url` that raced it) stashed
+/// before the listener existed. Android and iOS can also deliver a pending
+/// link when the app is resumed from the background rather than freshly
+/// started — in that case the stash above is already empty, so this falls
+/// back to asking the plugin directly via `get_current()`. The frontend
+/// should call this on resume too (e.g. from a Capacitor-style `resume`
+/// lifecycle hook).
+#[tauri::command]
+pub fn drain_pending_deep_link(
+    app: AppHandle,
+    pending: State<'_, PendingDeepLinks>,
+) -> Result<Vec<String>, String> {
+    pending.ready.store(true, Ordering::Relaxed);
+
+    let stashed = std::mem::take(&mut *pending.stash.lock().unwrap());
+    if !stashed.is_empty() {
+        return Ok(stashed);
+    }
+
+    let urls = app
+        .deep_link()
+        .get_current()
+        .map_err(|err| err.to_string())?
+        .unwrap_or_default();
+    #[cfg(mobile)]
+    let urls = crate::mobile::filter_deep_links(urls);
+
+    Ok(urls.iter().map(Url::to_string).collect())
+}
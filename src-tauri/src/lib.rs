@@ -1,5 +1,13 @@
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use tauri_plugin_deep_link::DeepLinkExt;
+use url::Url;
+
+mod auth;
+mod commands;
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(mobile)]
+mod mobile;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -8,25 +16,67 @@ pub fn run() {
     #[cfg(desktop)]
     {
         // Configure single instance plugin with deep-link feature
-        builder = builder.plugin(tauri_plugin_single_instance::init(|_app, argv, _cwd| {
-            println!("a new app instance was opened with {argv:?} and the deep link event was already triggered");
-            // You would need to manually check argv if using runtime-defined schemes
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            println!("a new app instance was opened with {argv:?}");
+
+            // On Windows and Linux the deep link arrives as a CLI argument to the
+            // second instance rather than through `on_open_url`, so scan argv for it.
+            if let Some(url) = argv.iter().find_map(|arg| {
+                Url::parse(arg)
+                    .ok()
+                    .filter(|url| url.scheme() == "statisfy")
+            }) {
+                println!("Deep link found in argv: {url}");
+                app.emit("deep-link", url).unwrap();
+            }
+
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.set_focus();
+            }
         }));
     }
 
     builder = builder
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
+        .invoke_handler(tauri::generate_handler![
+            commands::is_deep_link_registered,
+            commands::register_deep_link,
+            commands::unregister_deep_link,
+            commands::drain_pending_deep_link,
+            auth::start_spotify_login,
+        ])
+        .manage(commands::PendingDeepLinks::default())
         .setup(|app| {
             // Store the app handle for later use with deep links
             let app_handle = app.handle().clone();
 
-            // Listen to deep link events
+            // Listen to deep link events. This single callback covers both the
+            // `statisfy://` custom scheme and, on Android/iOS, HTTPS universal
+            // links (filtered against the bundled associated-domain config in
+            // `mobile::filter_deep_links` — see `mobile.rs` — rather than
+            // trusting every `https` URL the plugin hands us).
+            //
+            // If this fires during the same `setup()` window that runs below,
+            // the frontend hasn't loaded far enough to have called `listen`
+            // yet, and `emit` doesn't buffer for listeners that attach later.
+            // So route through the same `PendingDeepLinks` stash as the
+            // cold-start pickup below, and only emit directly once the
+            // frontend has signaled (via `drain_pending_deep_link`) that it's
+            // actually listening.
             app.deep_link().on_open_url(move |event| {
                 let urls = event.urls();
                 println!("Deep link event received: {:?}", urls);
+                #[cfg(mobile)]
+                let urls = mobile::filter_deep_links(urls);
+
+                let pending = app_handle.state::<commands::PendingDeepLinks>();
                 for url in urls {
-                    app_handle.emit("deep-link", url).unwrap();
+                    if pending.is_ready() {
+                        app_handle.emit("deep-link", url).unwrap();
+                    } else {
+                        pending.stash(url.to_string());
+                    }
                 }
             });
 
@@ -37,6 +87,30 @@ pub fn run() {
                     Ok(_) => println!("Registered statisfy:// protocol handler"),
                     Err(err) => eprintln!("Failed to register protocol handler: {}", err),
                 }
+
+                #[cfg(target_os = "linux")]
+                match linux::register_desktop_entry() {
+                    Ok(_) => println!("Registered statisfy.desktop as the x-scheme-handler/statisfy handler"),
+                    Err(err) => eprintln!("Failed to register statisfy.desktop: {}", err),
+                }
+            }
+
+            // The app may have been launched fresh by a `statisfy://` link (e.g. on
+            // macOS), in which case the URL never reaches `on_open_url` above. Stash
+            // it the same way, for the same reason.
+            //
+            // On mobile this only covers the cold-launch case; resuming from the
+            // background delivers the pending link differently, so the frontend
+            // drains it the same way on resume.
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                println!("Deep link present at launch: {:?}", urls);
+                #[cfg(mobile)]
+                let urls = mobile::filter_deep_links(urls);
+
+                let pending = app.state::<commands::PendingDeepLinks>();
+                for url in urls {
+                    pending.stash(url.to_string());
+                }
             }
 
             Ok(())
@@ -0,0 +1,53 @@
+//! Linux-only persistent registration of the `statisfy://` scheme.
+//!
+//! `DeepLinkExt::register` is a no-op on most Linux desktops: the OS resolves
+//! `x-scheme-handler/statisfy` through XDG desktop entries, not a runtime API.
+//! This writes a `.desktop` file and tells `update-desktop-database` about it
+//! so the association survives a reboot.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+const DESKTOP_FILE_NAME: &str = "statisfy.desktop";
+
+fn applications_dir() -> io::Result<PathBuf> {
+    let home = std::env::var("HOME").map_err(|_| io::Error::other("HOME is not set"))?;
+    Ok(PathBuf::from(home).join(".local/share/applications"))
+}
+
+/// Writes `~/.local/share/applications/statisfy.desktop` advertising this
+/// executable as the handler for `x-scheme-handler/statisfy`, then refreshes
+/// the desktop database so the association takes effect immediately.
+pub fn register_desktop_entry() -> io::Result<()> {
+    let exe_path = std::env::current_exe()?;
+    let exe = exe_path.to_string_lossy();
+
+    let dir = applications_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    // Quote the executable path: per the Desktop Entry spec, an unquoted path
+    // containing a space (e.g. under `~/Applications/My App/`) makes the
+    // `Exec=` line invalid and the handler silently fails to register.
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Name=Statisfy\n\
+         Exec=\"{exe}\" %u\n\
+         Type=Application\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/statisfy;\n"
+    );
+    fs::write(dir.join(DESKTOP_FILE_NAME), entry)?;
+
+    let status = Command::new("update-desktop-database")
+        .arg(dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "update-desktop-database exited with {status}"
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Mobile-only universal-link / app-link handling for `https://statisfy.app/...`
+//! links, on top of the `statisfy://` custom scheme that `on_open_url` in
+//! `lib.rs` already handles uniformly across platforms.
+//!
+//! Android and iOS only deliver these HTTPS links to the app for paths that
+//! match the associated-domain config hosted at the domain's `.well-known/`
+//! path: `apple-app-site-association` on iOS, `assetlinks.json` (plus the
+//! app's manifest intent filter) on Android. The files under `mobile/` mirror
+//! what's hosted there — keep them in sync with the real
+//! `https://statisfy.app/.well-known/...` copies. iOS's AASA is the one that
+//! actually enumerates path prefixes, so it's the shared source of truth for
+//! the runtime check below; Android scopes by the manifest's intent filter
+//! and uses `assetlinks.json` only to prove domain ownership.
+
+use serde::Deserialize;
+use url::Url;
+
+const APPLE_APP_SITE_ASSOCIATION: &str = include_str!("../mobile/apple-app-site-association");
+
+#[derive(Deserialize)]
+struct AppleAppSiteAssociation {
+    applinks: AppLinks,
+}
+
+#[derive(Deserialize)]
+struct AppLinks {
+    details: Vec<AppLinkDetail>,
+}
+
+#[derive(Deserialize)]
+struct AppLinkDetail {
+    components: Vec<PathComponent>,
+}
+
+#[derive(Deserialize)]
+struct PathComponent {
+    #[serde(rename = "/")]
+    path: String,
+}
+
+/// Path prefixes this app is registered to handle as universal/app links,
+/// read from the bundled `apple-app-site-association` file.
+pub fn associated_path_prefixes() -> Vec<String> {
+    let parsed: AppleAppSiteAssociation = match serde_json::from_str(APPLE_APP_SITE_ASSOCIATION) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("failed to parse bundled apple-app-site-association: {err}");
+            return Vec::new();
+        }
+    };
+
+    parsed
+        .applinks
+        .details
+        .into_iter()
+        .flat_map(|detail| detail.components)
+        .map(|component| component.path)
+        .collect()
+}
+
+/// Whether `url`'s path falls under one of the declared associated-domain
+/// path prefixes, treating a trailing `*` as a wildcard the way AASA does.
+pub fn matches_associated_domain(url: &Url, prefixes: &[String]) -> bool {
+    let path = url.path();
+    prefixes.iter().any(|prefix| match prefix.strip_suffix('*') {
+        Some(stripped) => path.starts_with(stripped),
+        None => path == prefix,
+    })
+}
+
+/// Drops any `https` link whose path falls outside the bundled
+/// associated-domain config; the `statisfy://` custom scheme always passes
+/// through unchanged.
+///
+/// Every code path that can hand the app a deep link — the live
+/// `on_open_url` callback, the cold-start/resume `get_current()` pickup, and
+/// `commands::drain_pending_deep_link`'s own `get_current()` fallback — must
+/// run URLs through this before treating them as trusted, so an out-of-scope
+/// `https` link can't sneak in via whichever path happens to skip the check.
+pub fn filter_deep_links(urls: Vec<Url>) -> Vec<Url> {
+    let prefixes = associated_path_prefixes();
+    urls.into_iter()
+        .filter(|url| {
+            let allowed = url.scheme() != "https" || matches_associated_domain(url, &prefixes);
+            if !allowed {
+                eprintln!("ignoring https link outside associated-domain path prefixes: {url}");
+            }
+            allowed
+        })
+        .collect()
+}